@@ -0,0 +1,124 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use std::ops::{Add, Sub, Mul};
+
+/// An angle, internally stored as radians, that can be constructed from either radians or
+/// degrees so that callers don't have to convert degree values by hand before feeding them
+/// into the 2d transform factories.
+pub struct Angle<T> {
+    radians: T,
+}
+
+impl<T: Copy> Angle<T> {
+    /// Create an angle from a value in radians.
+    pub fn radians(radians: T) -> Angle<T> {
+        Angle { radians: radians }
+    }
+
+    /// Returns this angle's value in radians.
+    ///
+    /// Named `get` rather than `radians` because `Angle::radians` already names the
+    /// constructor above it, and inherent impls can't have two items sharing one name.
+    pub fn get(&self) -> T {
+        self.radians
+    }
+
+    /// Deprecated alias for `Angle::radians`, kept so that old `Radians::new` call sites
+    /// keep compiling.
+    #[deprecated(note = "use Angle::radians instead")]
+    pub fn new(radians: T) -> Angle<T> {
+        Angle::radians(radians)
+    }
+}
+
+impl Angle<f32> {
+    /// Create an angle from a value in degrees.
+    pub fn degrees(degrees: f32) -> Angle<f32> {
+        Angle::radians(degrees * ::std::f32::consts::PI / 180.0)
+    }
+
+    /// Returns this angle's value in degrees. Named `to_degrees` for the same reason
+    /// `get` is named that instead of `radians`: `Angle::degrees` already names the
+    /// constructor.
+    pub fn to_degrees(&self) -> f32 {
+        self.radians * 180.0 / ::std::f32::consts::PI
+    }
+}
+
+impl Angle<f64> {
+    /// Create an angle from a value in degrees.
+    pub fn degrees(degrees: f64) -> Angle<f64> {
+        Angle::radians(degrees * ::std::f64::consts::PI / 180.0)
+    }
+
+    /// Returns this angle's value in degrees. See `Angle<f32>::to_degrees` for why this
+    /// isn't named `degrees`.
+    pub fn to_degrees(&self) -> f64 {
+        self.radians * 180.0 / ::std::f64::consts::PI
+    }
+}
+
+impl<T: Copy> Clone for Angle<T> {
+    fn clone(&self) -> Angle<T> { *self }
+}
+
+impl<T: Copy> Copy for Angle<T> {}
+
+impl<T: PartialEq> PartialEq for Angle<T> {
+    fn eq(&self, other: &Angle<T>) -> bool {
+        self.radians == other.radians
+    }
+}
+
+impl<T: Add<T, Output=T>> Add for Angle<T> {
+    type Output = Angle<T>;
+    fn add(self, other: Angle<T>) -> Angle<T> {
+        Angle { radians: self.radians + other.radians }
+    }
+}
+
+impl<T: Sub<T, Output=T>> Sub for Angle<T> {
+    type Output = Angle<T>;
+    fn sub(self, other: Angle<T>) -> Angle<T> {
+        Angle { radians: self.radians - other.radians }
+    }
+}
+
+impl<T: Mul<T, Output=T> + Copy> Mul<T> for Angle<T> {
+    type Output = Angle<T>;
+    fn mul(self, rhs: T) -> Angle<T> {
+        Angle { radians: self.radians * rhs }
+    }
+}
+
+/// Deprecated alias for `Angle`. New code should use `Angle::radians`/`Angle::degrees`.
+#[deprecated(note = "use Angle instead")]
+pub type Radians<T> = Angle<T>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_degrees_radians_roundtrip() {
+        let a = Angle::<f32>::degrees(90.0);
+        assert!((a.get() - ::std::f32::consts::FRAC_PI_2).abs() < 0.0001);
+        assert!((a.to_degrees() - 90.0).abs() < 0.0001);
+    }
+
+    #[test]
+    fn test_add_sub() {
+        let a = Angle::radians(1.0f32);
+        let b = Angle::radians(0.5f32);
+        assert_eq!((a + b).get(), 1.5);
+        assert_eq!((a - b).get(), 0.5);
+        assert_eq!((a * 2.0).get(), 2.0);
+    }
+}
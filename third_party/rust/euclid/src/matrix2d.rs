@@ -7,11 +7,14 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use super::{UnknownUnit, Radians};
+use super::UnknownUnit;
+use angle::Angle;
 use num::{One, Zero};
 use point::TypedPoint2D;
 use rect::TypedRect;
 use size::TypedSize2D;
+use vector::TypedVector2D;
+use matrix4d::TypedMatrix4D;
 use std::ops::{Add, Mul, Div, Sub};
 use std::marker::PhantomData;
 use approxeq::ApproxEq;
@@ -43,6 +46,7 @@ pub type Matrix2D<T> = TypedMatrix2D<T, UnknownUnit, UnknownUnit>;
 
 impl<T: Copy, Src, Dst> TypedMatrix2D<T, Src, Dst> {
     /// Create a matrix specifying its components in row-major order.
+    #[must_use]
     pub fn row_major(m11: T, m12: T, m21: T, m22: T, m31: T, m32: T) -> TypedMatrix2D<T, Src, Dst> {
         TypedMatrix2D {
             m11: m11, m12: m12,
@@ -53,6 +57,7 @@ impl<T: Copy, Src, Dst> TypedMatrix2D<T, Src, Dst> {
     }
 
     /// Create a matrix specifying its components in column-major order.
+    #[must_use]
     pub fn column_major(m11: T, m21: T, m31: T, m12: T, m22: T, m32: T) -> TypedMatrix2D<T, Src, Dst> {
         TypedMatrix2D {
             m11: m11, m12: m12,
@@ -81,6 +86,7 @@ impl<T: Copy, Src, Dst> TypedMatrix2D<T, Src, Dst> {
     }
 
     /// Drop the units, preserving only the numeric value.
+    #[must_use]
     pub fn to_untyped(&self) -> Matrix2D<T> {
         Matrix2D::row_major(
             self.m11, self.m12,
@@ -90,6 +96,7 @@ impl<T: Copy, Src, Dst> TypedMatrix2D<T, Src, Dst> {
     }
 
     /// Tag a unitless value with units.
+    #[must_use]
     pub fn from_untyped(p: &Matrix2D<T>) -> TypedMatrix2D<T, Src, Dst> {
         TypedMatrix2D::row_major(
             p.m11, p.m12,
@@ -97,6 +104,19 @@ impl<T: Copy, Src, Dst> TypedMatrix2D<T, Src, Dst> {
             p.m31, p.m32
         )
     }
+
+    /// Lifts this 2d affine matrix into the equivalent 4 by 4 matrix, for interoperating
+    /// with 3d transform pipelines.
+    #[must_use]
+    pub fn to_3d(&self) -> TypedMatrix4D<T, Src, Dst> where T: Zero + One {
+        let (_0, _1) = (Zero::zero(), One::one());
+        TypedMatrix4D::row_major(
+            self.m11, self.m12, _0, _0,
+            self.m21, self.m22, _0, _0,
+                  _0,       _0, _1, _0,
+            self.m31, self.m32, _0, _1,
+        )
+    }
 }
 
 impl<T, Src, Dst> TypedMatrix2D<T, Src, Dst>
@@ -109,6 +129,7 @@ where T: Copy + Clone +
          PartialOrd +
          One + Zero  {
 
+    #[must_use]
     pub fn identity() -> TypedMatrix2D<T, Src, Dst> {
         let (_0, _1) = (Zero::zero(), One::one());
         TypedMatrix2D::row_major(
@@ -118,8 +139,21 @@ where T: Copy + Clone +
         )
     }
 
+    /// Returns true if this matrix is the identity matrix.
+    pub fn is_identity(&self) -> bool {
+        *self == TypedMatrix2D::identity()
+    }
+
+    /// Returns true if this matrix has a non-zero translation, i.e. if `transform_point`
+    /// and `transform_vector` would disagree on the same `(x, y)` components.
+    pub fn has_translation(&self) -> bool {
+        let _0 = Zero::zero();
+        self.m31 != _0 || self.m32 != _0
+    }
+
     /// Returns the multiplication of the two matrices such that mat's transformation
     /// applies after self's transformation.
+    #[must_use]
     pub fn post_mul<NewDst>(&self, mat: &TypedMatrix2D<T, Dst, NewDst>) -> TypedMatrix2D<T, Src, NewDst> {
         TypedMatrix2D::row_major(
             self.m11 * mat.m11 + self.m12 * mat.m21,
@@ -133,11 +167,13 @@ where T: Copy + Clone +
 
     /// Returns the multiplication of the two matrices such that mat's transformation
     /// applies before self's transformation.
+    #[must_use]
     pub fn pre_mul<NewSrc>(&self, mat: &TypedMatrix2D<T, NewSrc, Src>) -> TypedMatrix2D<T, NewSrc, Dst> {
         mat.post_mul(self)
     }
 
     /// Returns a translation matrix.
+    #[must_use]
     pub fn create_translation(x: T, y: T) -> TypedMatrix2D<T, Src, Dst> {
          let (_0, _1): (T, T) = (Zero::zero(), One::one());
          TypedMatrix2D::row_major(
@@ -148,16 +184,19 @@ where T: Copy + Clone +
     }
 
     /// Applies a translation after self's transformation and returns the resulting matrix.
+    #[must_use]
     pub fn post_translated(&self, x: T, y: T) -> TypedMatrix2D<T, Src, Dst> {
         self.post_mul(&TypedMatrix2D::create_translation(x, y))
     }
 
     /// Applies a translation before self's transformation and returns the resulting matrix.
+    #[must_use]
     pub fn pre_translated(&self, x: T, y: T) -> TypedMatrix2D<T, Src, Dst> {
         self.pre_mul(&TypedMatrix2D::create_translation(x, y))
     }
 
     /// Returns a scale matrix.
+    #[must_use]
     pub fn create_scale(x: T, y: T) -> TypedMatrix2D<T, Src, Dst> {
         let _0 = Zero::zero();
         TypedMatrix2D::row_major(
@@ -168,11 +207,13 @@ where T: Copy + Clone +
     }
 
     /// Applies a scale after self's transformation and returns the resulting matrix.
+    #[must_use]
     pub fn post_scaled(&self, x: T, y: T) -> TypedMatrix2D<T, Src, Dst> {
         self.post_mul(&TypedMatrix2D::create_scale(x, y))
     }
 
     /// Applies a scale before self's transformation and returns the resulting matrix.
+    #[must_use]
     pub fn pre_scaled(&self, x: T, y: T) -> TypedMatrix2D<T, Src, Dst> {
         TypedMatrix2D::row_major(
             self.m11 * x, self.m12,
@@ -182,7 +223,8 @@ where T: Copy + Clone +
     }
 
     /// Returns a rotation matrix.
-    pub fn create_rotation(theta: Radians<T>) -> TypedMatrix2D<T, Src, Dst> {
+    #[must_use]
+    pub fn create_rotation(theta: Angle<T>) -> TypedMatrix2D<T, Src, Dst> {
         let _0 = Zero::zero();
         let cos = theta.get().cos();
         let sin = theta.get().sin();
@@ -194,15 +236,42 @@ where T: Copy + Clone +
     }
 
     /// Applies a rotation after self's transformation and returns the resulting matrix.
-    pub fn post_rotated(&self, theta: Radians<T>) -> TypedMatrix2D<T, Src, Dst> {
+    #[must_use]
+    pub fn post_rotated(&self, theta: Angle<T>) -> TypedMatrix2D<T, Src, Dst> {
         self.post_mul(&TypedMatrix2D::create_rotation(theta))
     }
 
     /// Applies a rotation after self's transformation and returns the resulting matrix.
-    pub fn pre_rotated(&self, theta: Radians<T>) -> TypedMatrix2D<T, Src, Dst> {
+    #[must_use]
+    pub fn pre_rotated(&self, theta: Angle<T>) -> TypedMatrix2D<T, Src, Dst> {
         self.pre_mul(&TypedMatrix2D::create_rotation(theta))
     }
 
+    /// Returns a skew matrix, as used by the CSS `skew()` transform and italic text synthesis.
+    #[must_use]
+    pub fn create_skew(alpha: Angle<T>, beta: Angle<T>) -> TypedMatrix2D<T, Src, Dst> {
+        let (_0, _1) = (Zero::zero(), One::one());
+        let tan_alpha = alpha.get().tan();
+        let tan_beta = beta.get().tan();
+        TypedMatrix2D::row_major(
+             _1,       tan_beta,
+            tan_alpha,  _1,
+             _0,        _0
+        )
+    }
+
+    /// Applies a skew after self's transformation and returns the resulting matrix.
+    #[must_use]
+    pub fn post_skewed(&self, alpha: Angle<T>, beta: Angle<T>) -> TypedMatrix2D<T, Src, Dst> {
+        self.post_mul(&TypedMatrix2D::create_skew(alpha, beta))
+    }
+
+    /// Applies a skew before self's transformation and returns the resulting matrix.
+    #[must_use]
+    pub fn pre_skewed(&self, alpha: Angle<T>, beta: Angle<T>) -> TypedMatrix2D<T, Src, Dst> {
+        self.pre_mul(&TypedMatrix2D::create_skew(alpha, beta))
+    }
+
     /// Returns the given point transformed by this matrix.
     #[inline]
     pub fn transform_point(&self, point: &TypedPoint2D<T, Src>) -> TypedPoint2D<T, Dst> {
@@ -210,6 +279,16 @@ where T: Copy + Clone +
                           point.x * self.m12 + point.y * self.m22 + self.m32)
     }
 
+    /// Returns the given vector transformed by this matrix, applying only the linear part
+    /// (the `m11`..`m22` terms) and omitting translation. Use this instead of
+    /// `transform_point` for directions, normals, velocities or other displacements, which
+    /// should not be affected by the matrix's translation.
+    #[inline]
+    pub fn transform_vector(&self, vector: &TypedVector2D<T, Src>) -> TypedVector2D<T, Dst> {
+        TypedVector2D::new(vector.x * self.m11 + vector.y * self.m21,
+                           vector.x * self.m12 + vector.y * self.m22)
+    }
+
     /// Returns a rectangle that encompasses the result of transforming the given rectangle by this
     /// matrix.
     #[inline]
@@ -267,6 +346,7 @@ where T: Copy + Clone +
 
     /// Returns the same matrix with a different destination unit.
     #[inline]
+    #[must_use]
     pub fn with_destination<NewDst>(&self) -> TypedMatrix2D<T, Src, NewDst> {
         TypedMatrix2D::row_major(
             self.m11, self.m12,
@@ -277,6 +357,7 @@ where T: Copy + Clone +
 
     /// Returns the same matrix with a different source unit.
     #[inline]
+    #[must_use]
     pub fn with_source<NewSrc>(&self) -> TypedMatrix2D<T, NewSrc, Dst> {
         TypedMatrix2D::row_major(
             self.m11, self.m12,
@@ -286,6 +367,38 @@ where T: Copy + Clone +
     }
 }
 
+/// `a * b` is equivalent to `a.post_mul(&b)`: `b`'s transformation applies after `a`'s.
+impl<T, Src, Dst, NewDst> Mul<TypedMatrix2D<T, Dst, NewDst>> for TypedMatrix2D<T, Src, Dst>
+where T: Copy + Clone +
+         Add<T, Output=T> +
+         Mul<T, Output=T> +
+         Div<T, Output=T> +
+         Sub<T, Output=T> +
+         Trig +
+         PartialOrd +
+         One + Zero {
+    type Output = TypedMatrix2D<T, Src, NewDst>;
+    fn mul(self, rhs: TypedMatrix2D<T, Dst, NewDst>) -> TypedMatrix2D<T, Src, NewDst> {
+        self.post_mul(&rhs)
+    }
+}
+
+/// `matrix * point` transforms `point` by `matrix`, equivalent to `matrix.transform_point(&point)`.
+impl<T, Src, Dst> Mul<TypedPoint2D<T, Src>> for TypedMatrix2D<T, Src, Dst>
+where T: Copy + Clone +
+         Add<T, Output=T> +
+         Mul<T, Output=T> +
+         Div<T, Output=T> +
+         Sub<T, Output=T> +
+         Trig +
+         PartialOrd +
+         One + Zero {
+    type Output = TypedPoint2D<T, Dst>;
+    fn mul(self, rhs: TypedPoint2D<T, Src>) -> TypedPoint2D<T, Dst> {
+        self.transform_point(&rhs)
+    }
+}
+
 impl<T: ApproxEq<T>, Src, Dst> TypedMatrix2D<T, Src, Dst> {
     pub fn approx_eq(&self, other: &Self) -> bool {
         self.m11.approx_eq(&other.m11) && self.m12.approx_eq(&other.m12) &&
@@ -300,18 +413,34 @@ impl<T: Copy + fmt::Debug, Src, Dst> fmt::Debug for TypedMatrix2D<T, Src, Dst> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: Copy + serde::Serialize, Src, Dst> serde::Serialize for TypedMatrix2D<T, Src, Dst> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_row_major_array().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Copy + serde::Deserialize<'de>, Src, Dst> serde::Deserialize<'de> for TypedMatrix2D<T, Src, Dst> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [m11, m12, m21, m22, m31, m32] = try!(<[T; 6]>::deserialize(deserializer));
+        Ok(TypedMatrix2D::row_major(m11, m12, m21, m22, m31, m32))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use approxeq::ApproxEq;
     use point::Point2D;
-    use Radians;
+    use vector::Vector2D;
+    use angle::Angle;
 
     use std::f32::consts::FRAC_PI_2;
 
     type Mat = Matrix2D<f32>;
 
-    fn rad(v: f32) -> Radians<f32> { Radians::new(v) }
+    fn rad(v: f32) -> Angle<f32> { Angle::radians(v) }
 
     #[test]
     pub fn test_translation() {
@@ -350,6 +479,24 @@ mod test {
         assert!(s1.transform_point(&Point2D::new(2.0, 2.0)).approx_eq(&Point2D::new(4.0, 6.0)));
     }
 
+    #[test]
+    pub fn test_skew() {
+        let identity = Mat::create_skew(rad(0.0), rad(0.0));
+        assert!(identity.approx_eq(&Mat::identity()));
+
+        let skew = Mat::create_skew(rad(1.0), rad(0.5));
+        let unit_square = [
+            Point2D::new(0.0, 0.0),
+            Point2D::new(1.0, 0.0),
+            Point2D::new(1.0, 1.0),
+            Point2D::new(0.0, 1.0),
+        ];
+        let skewed: Vec<_> = unit_square.iter().map(|p| skew.transform_point(p)).collect();
+
+        assert!(skewed[0].approx_eq(&Point2D::new(0.0, 0.0)));
+        assert!(skewed[2].x != skewed[2].y);
+    }
+
     #[test]
     fn test_column_major() {
         assert_eq!(
@@ -412,10 +559,48 @@ mod test {
         assert!(t.pre_mul(&r).transform_point(&a).approx_eq(&t.transform_point(&r.transform_point(&a))));
     }
 
+    #[test]
+    pub fn test_transform_vector() {
+        let t = Mat::create_translation(1.0, 2.0).post_scaled(3.0, 4.0);
+
+        assert!(!t.is_identity());
+        assert!(Mat::identity().is_identity());
+
+        assert!(t.has_translation());
+        assert!(!Mat::create_scale(3.0, 4.0).has_translation());
+
+        // A translating matrix moves a point, but a vector only goes through the scale.
+        assert_eq!(
+            t.transform_vector(&Vector2D::new(2.0, 2.0)),
+            Mat::create_scale(3.0, 4.0).transform_vector(&Vector2D::new(2.0, 2.0))
+        );
+        assert!(t.transform_point(&Point2D::new(0.0, 0.0)) != Point2D::new(0.0, 0.0));
+    }
+
+    #[test]
+    pub fn test_mul_operator() {
+        let r = Mat::create_rotation(rad(FRAC_PI_2));
+        let t = Mat::create_translation(2.0, 3.0);
+
+        assert_eq!(r * t, r.post_mul(&t));
+
+        let a = Point2D::new(1.0, 1.0);
+        assert!((r * a).approx_eq(&r.transform_point(&a)));
+    }
+
     #[test]
     fn test_size_of() {
         use std::mem::size_of;
         assert_eq!(size_of::<Matrix2D<f32>>(), 6*size_of::<f32>());
         assert_eq!(size_of::<Matrix2D<f64>>(), 6*size_of::<f64>());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let m = Mat::create_rotation(rad(FRAC_PI_2)).post_translated(1.0, 2.0);
+        let json = ::serde_json::to_string(&m).unwrap();
+        let deserialized: Mat = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(m, deserialized);
+    }
 }
\ No newline at end of file
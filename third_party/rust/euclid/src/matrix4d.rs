@@ -0,0 +1,588 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::UnknownUnit;
+use angle::Angle;
+use num::{One, Zero};
+use point::TypedPoint2D;
+use point3d::TypedPoint3D;
+use std::ops::{Add, Mul, Div, Sub};
+use std::marker::PhantomData;
+use approxeq::ApproxEq;
+use trig::Trig;
+use std::fmt;
+
+/// A 3d transform stored as a 4 by 4 matrix in row-major order in memory, useful to represent
+/// perspective transformations and arbitrary 3d affine transformations.
+///
+/// Matrices can be parametrized over the source and destination units, to describe a
+/// transformation from a space to another. For example,
+/// TypedMatrix4D<f32, WorldSpace, ScreenSpace>::transform_point3d takes a
+/// TypedPoint3D<f32, WorldSpace> and returns a TypedPoint3D<f32, ScreenSpace>.
+///
+/// Points and vectors are treated as row vectors, so the translation terms are stored in the
+/// last row of the matrix, mirroring the convention used by TypedMatrix2D.
+pub struct TypedMatrix4D<T, Src, Dst> {
+    pub m11: T, pub m12: T, pub m13: T, pub m14: T,
+    pub m21: T, pub m22: T, pub m23: T, pub m24: T,
+    pub m31: T, pub m32: T, pub m33: T, pub m34: T,
+    pub m41: T, pub m42: T, pub m43: T, pub m44: T,
+    _unit: PhantomData<(Src, Dst)>,
+}
+
+/// The default 3d matrix type with no units.
+pub type Matrix4D<T> = TypedMatrix4D<T, UnknownUnit, UnknownUnit>;
+
+/// An undivided homogeneous point, the raw result of transforming a point through a
+/// TypedMatrix4D before the perspective divide by `w` is applied.
+pub struct HomogeneousVector<T, U> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    pub w: T,
+    _unit: PhantomData<U>,
+}
+
+pub type Vector4D<T> = HomogeneousVector<T, UnknownUnit>;
+
+impl<T: Copy, U> Clone for HomogeneousVector<T, U> {
+    fn clone(&self) -> HomogeneousVector<T, U> { *self }
+}
+
+impl<T: Copy, U> Copy for HomogeneousVector<T, U> {}
+
+impl<T: PartialEq, U> PartialEq<HomogeneousVector<T, U>> for HomogeneousVector<T, U> {
+    fn eq(&self, other: &HomogeneousVector<T, U>) -> bool {
+        self.x == other.x && self.y == other.y && self.z == other.z && self.w == other.w
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for HomogeneousVector<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?},{:?},{:?},{:?})", self.x, self.y, self.z, self.w)
+    }
+}
+
+impl<T: Copy, U> HomogeneousVector<T, U> {
+    /// Constructor.
+    pub fn new(x: T, y: T, z: T, w: T) -> HomogeneousVector<T, U> {
+        HomogeneousVector { x: x, y: y, z: z, w: w, _unit: PhantomData }
+    }
+}
+
+impl<T, U> HomogeneousVector<T, U>
+where T: Copy + Clone + Add<T, Output=T> + Mul<T, Output=T> + Div<T, Output=T> + Sub<T, Output=T> + PartialEq + Zero {
+    /// Divides x, y and z by w, returning None if w is zero.
+    pub fn to_point3d(&self) -> Option<TypedPoint3D<T, U>> {
+        let _0: T = Zero::zero();
+        if self.w == _0 {
+            return None;
+        }
+        Some(TypedPoint3D::new(self.x / self.w, self.y / self.w, self.z / self.w))
+    }
+
+    /// Divides x and y by w, dropping z, returning None if w is zero.
+    pub fn to_point2d(&self) -> Option<TypedPoint2D<T, U>> {
+        let _0: T = Zero::zero();
+        if self.w == _0 {
+            return None;
+        }
+        Some(TypedPoint2D::new(self.x / self.w, self.y / self.w))
+    }
+}
+
+impl<T: Copy, Src, Dst> TypedMatrix4D<T, Src, Dst> {
+    /// Create a matrix specifying its components in row-major order.
+    pub fn row_major(
+        m11: T, m12: T, m13: T, m14: T,
+        m21: T, m22: T, m23: T, m24: T,
+        m31: T, m32: T, m33: T, m34: T,
+        m41: T, m42: T, m43: T, m44: T,
+    ) -> TypedMatrix4D<T, Src, Dst> {
+        TypedMatrix4D {
+            m11: m11, m12: m12, m13: m13, m14: m14,
+            m21: m21, m22: m22, m23: m23, m24: m24,
+            m31: m31, m32: m32, m33: m33, m34: m34,
+            m41: m41, m42: m42, m43: m43, m44: m44,
+            _unit: PhantomData,
+        }
+    }
+
+    /// Create a matrix specifying its components in column-major order.
+    pub fn column_major(
+        m11: T, m21: T, m31: T, m41: T,
+        m12: T, m22: T, m32: T, m42: T,
+        m13: T, m23: T, m33: T, m43: T,
+        m14: T, m24: T, m34: T, m44: T,
+    ) -> TypedMatrix4D<T, Src, Dst> {
+        TypedMatrix4D::row_major(
+            m11, m12, m13, m14,
+            m21, m22, m23, m24,
+            m31, m32, m33, m34,
+            m41, m42, m43, m44,
+        )
+    }
+
+    /// Returns an array containing this matrix's terms in row-major order.
+    pub fn to_row_major_array(&self) -> [T; 16] {
+        [
+            self.m11, self.m12, self.m13, self.m14,
+            self.m21, self.m22, self.m23, self.m24,
+            self.m31, self.m32, self.m33, self.m34,
+            self.m41, self.m42, self.m43, self.m44,
+        ]
+    }
+
+    /// Returns an array containing this matrix's terms in column-major order.
+    pub fn to_column_major_array(&self) -> [T; 16] {
+        [
+            self.m11, self.m21, self.m31, self.m41,
+            self.m12, self.m22, self.m32, self.m42,
+            self.m13, self.m23, self.m33, self.m43,
+            self.m14, self.m24, self.m34, self.m44,
+        ]
+    }
+
+    /// Drop the units, preserving only the numeric value.
+    pub fn to_untyped(&self) -> Matrix4D<T> {
+        Matrix4D::row_major(
+            self.m11, self.m12, self.m13, self.m14,
+            self.m21, self.m22, self.m23, self.m24,
+            self.m31, self.m32, self.m33, self.m34,
+            self.m41, self.m42, self.m43, self.m44,
+        )
+    }
+
+    /// Tag a unitless value with units.
+    pub fn from_untyped(m: &Matrix4D<T>) -> TypedMatrix4D<T, Src, Dst> {
+        TypedMatrix4D::row_major(
+            m.m11, m.m12, m.m13, m.m14,
+            m.m21, m.m22, m.m23, m.m24,
+            m.m31, m.m32, m.m33, m.m34,
+            m.m41, m.m42, m.m43, m.m44,
+        )
+    }
+}
+
+impl<T, Src, Dst> TypedMatrix4D<T, Src, Dst>
+where T: Copy + Clone +
+         Add<T, Output=T> +
+         Mul<T, Output=T> +
+         Div<T, Output=T> +
+         Sub<T, Output=T> +
+         Trig +
+         PartialOrd +
+         PartialEq +
+         One + Zero {
+
+    pub fn identity() -> TypedMatrix4D<T, Src, Dst> {
+        let (_0, _1) = (Zero::zero(), One::one());
+        TypedMatrix4D::row_major(
+            _1, _0, _0, _0,
+            _0, _1, _0, _0,
+            _0, _0, _1, _0,
+            _0, _0, _0, _1,
+        )
+    }
+
+    /// Returns true if this matrix is the identity matrix.
+    pub fn is_identity(&self) -> bool {
+        *self == TypedMatrix4D::identity()
+    }
+
+    /// Returns the multiplication of the two matrices such that mat's transformation applies
+    /// after self's transformation.
+    pub fn post_mul<NewDst>(&self, mat: &TypedMatrix4D<T, Dst, NewDst>) -> TypedMatrix4D<T, Src, NewDst> {
+        TypedMatrix4D::row_major(
+            self.m11 * mat.m11 + self.m12 * mat.m21 + self.m13 * mat.m31 + self.m14 * mat.m41,
+            self.m11 * mat.m12 + self.m12 * mat.m22 + self.m13 * mat.m32 + self.m14 * mat.m42,
+            self.m11 * mat.m13 + self.m12 * mat.m23 + self.m13 * mat.m33 + self.m14 * mat.m43,
+            self.m11 * mat.m14 + self.m12 * mat.m24 + self.m13 * mat.m34 + self.m14 * mat.m44,
+
+            self.m21 * mat.m11 + self.m22 * mat.m21 + self.m23 * mat.m31 + self.m24 * mat.m41,
+            self.m21 * mat.m12 + self.m22 * mat.m22 + self.m23 * mat.m32 + self.m24 * mat.m42,
+            self.m21 * mat.m13 + self.m22 * mat.m23 + self.m23 * mat.m33 + self.m24 * mat.m43,
+            self.m21 * mat.m14 + self.m22 * mat.m24 + self.m23 * mat.m34 + self.m24 * mat.m44,
+
+            self.m31 * mat.m11 + self.m32 * mat.m21 + self.m33 * mat.m31 + self.m34 * mat.m41,
+            self.m31 * mat.m12 + self.m32 * mat.m22 + self.m33 * mat.m32 + self.m34 * mat.m42,
+            self.m31 * mat.m13 + self.m32 * mat.m23 + self.m33 * mat.m33 + self.m34 * mat.m43,
+            self.m31 * mat.m14 + self.m32 * mat.m24 + self.m33 * mat.m34 + self.m34 * mat.m44,
+
+            self.m41 * mat.m11 + self.m42 * mat.m21 + self.m43 * mat.m31 + self.m44 * mat.m41,
+            self.m41 * mat.m12 + self.m42 * mat.m22 + self.m43 * mat.m32 + self.m44 * mat.m42,
+            self.m41 * mat.m13 + self.m42 * mat.m23 + self.m43 * mat.m33 + self.m44 * mat.m43,
+            self.m41 * mat.m14 + self.m42 * mat.m24 + self.m43 * mat.m34 + self.m44 * mat.m44,
+        )
+    }
+
+    /// Returns the multiplication of the two matrices such that mat's transformation applies
+    /// before self's transformation.
+    pub fn pre_mul<NewSrc>(&self, mat: &TypedMatrix4D<T, NewSrc, Src>) -> TypedMatrix4D<T, NewSrc, Dst> {
+        mat.post_mul(self)
+    }
+
+    /// Returns a translation matrix.
+    pub fn create_translation(x: T, y: T, z: T) -> TypedMatrix4D<T, Src, Dst> {
+        let (_0, _1) = (Zero::zero(), One::one());
+        TypedMatrix4D::row_major(
+            _1, _0, _0, _0,
+            _0, _1, _0, _0,
+            _0, _0, _1, _0,
+             x,  y,  z, _1,
+        )
+    }
+
+    /// Returns a scale matrix.
+    pub fn create_scale(x: T, y: T, z: T) -> TypedMatrix4D<T, Src, Dst> {
+        let (_0, _1) = (Zero::zero(), One::one());
+        TypedMatrix4D::row_major(
+             x, _0, _0, _0,
+            _0,  y, _0, _0,
+            _0, _0,  z, _0,
+            _0, _0, _0, _1,
+        )
+    }
+
+    /// Returns a rotation matrix of `theta` about the axis given by the unit vector (x, y, z).
+    pub fn create_rotation(x: T, y: T, z: T, theta: Angle<T>) -> TypedMatrix4D<T, Src, Dst> {
+        let (_0, _1) = (Zero::zero(), One::one());
+        let c = theta.get().cos();
+        let s = theta.get().sin();
+        let t = _1 - c;
+
+        let tx = t * x;
+        let ty = t * y;
+        let tz = t * z;
+        let sx = s * x;
+        let sy = s * y;
+        let sz = s * z;
+
+        TypedMatrix4D::row_major(
+            tx * x + c,  tx * y - sz, tx * z + sy, _0,
+            tx * y + sz, ty * y + c,  ty * z - sx, _0,
+            tx * z - sy, ty * z + sx, tz * z + c,  _0,
+            _0,          _0,          _0,          _1,
+        )
+    }
+
+    /// Returns a perspective projection matrix with the projection plane at z = 0 and the
+    /// viewer's eye at (0, 0, d), looking down the negative z axis, so that `d` is the distance
+    /// from the eye to the projection plane. Points on the plane z = 0 are left unchanged.
+    pub fn create_perspective(d: T) -> TypedMatrix4D<T, Src, Dst> {
+        let (_0, _1) = (Zero::zero(), One::one());
+        let mut m = TypedMatrix4D::identity();
+        m.m34 = _0 - _1 / d;
+        m
+    }
+
+    /// Returns an orthographic projection matrix mapping the given box to the
+    /// `[-1, 1]` clip space cube.
+    pub fn create_orthographic(left: T, right: T, bottom: T, top: T, near: T, far: T) -> TypedMatrix4D<T, Src, Dst> {
+        let (_0, _1) = (Zero::zero(), One::one());
+        let two = _1 + _1;
+        TypedMatrix4D::row_major(
+            two / (right - left), _0, _0, _0,
+            _0, two / (top - bottom), _0, _0,
+            _0, _0, two / (far - near), _0,
+            _0 - (right + left) / (right - left),
+            _0 - (top + bottom) / (top - bottom),
+            _0 - (far + near) / (far - near),
+            _1,
+        )
+    }
+
+    /// Returns the homogeneous vector obtained by multiplying `(x, y, z, 1)` by this matrix, without
+    /// performing the perspective divide.
+    pub fn transform_point4d(&self, point: &TypedPoint3D<T, Src>) -> HomogeneousVector<T, Dst> {
+        HomogeneousVector::new(
+            point.x * self.m11 + point.y * self.m21 + point.z * self.m31 + self.m41,
+            point.x * self.m12 + point.y * self.m22 + point.z * self.m32 + self.m42,
+            point.x * self.m13 + point.y * self.m23 + point.z * self.m33 + self.m43,
+            point.x * self.m14 + point.y * self.m24 + point.z * self.m34 + self.m44,
+        )
+    }
+
+    /// Returns the given 3d point transformed by this matrix, performing the perspective
+    /// divide, or `None` if the transformed `w` is zero.
+    pub fn transform_point3d(&self, point: &TypedPoint3D<T, Src>) -> Option<TypedPoint3D<T, Dst>> {
+        self.transform_point4d(point).to_point3d()
+    }
+
+    /// Returns the given 2d point, embedded at `z = 0`, transformed by this matrix and
+    /// projected back down to 2d, or `None` if the transformed `w` is zero.
+    pub fn transform_point2d(&self, point: &TypedPoint2D<T, Src>) -> Option<TypedPoint2D<T, Dst>> {
+        let _0: T = Zero::zero();
+        self.transform_point4d(&TypedPoint3D::new(point.x, point.y, _0)).to_point2d()
+    }
+
+    /// Computes and returns the determinant of this matrix.
+    pub fn determinant(&self) -> T {
+        let s0 = self.m11 * self.m22 - self.m21 * self.m12;
+        let s1 = self.m11 * self.m23 - self.m21 * self.m13;
+        let s2 = self.m11 * self.m24 - self.m21 * self.m14;
+        let s3 = self.m12 * self.m23 - self.m22 * self.m13;
+        let s4 = self.m12 * self.m24 - self.m22 * self.m14;
+        let s5 = self.m13 * self.m24 - self.m23 * self.m14;
+
+        let c5 = self.m33 * self.m44 - self.m43 * self.m34;
+        let c4 = self.m32 * self.m44 - self.m42 * self.m34;
+        let c3 = self.m32 * self.m43 - self.m42 * self.m33;
+        let c2 = self.m31 * self.m44 - self.m41 * self.m34;
+        let c1 = self.m31 * self.m43 - self.m41 * self.m33;
+        let c0 = self.m31 * self.m42 - self.m41 * self.m32;
+
+        s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+    }
+
+    /// Returns the inverse matrix, computed via cofactor expansion, or `None` if this matrix
+    /// is not invertible.
+    pub fn inverse(&self) -> Option<TypedMatrix4D<T, Dst, Src>> {
+        let (m11, m12, m13, m14) = (self.m11, self.m12, self.m13, self.m14);
+        let (m21, m22, m23, m24) = (self.m21, self.m22, self.m23, self.m24);
+        let (m31, m32, m33, m34) = (self.m31, self.m32, self.m33, self.m34);
+        let (m41, m42, m43, m44) = (self.m41, self.m42, self.m43, self.m44);
+
+        let s0 = m11 * m22 - m21 * m12;
+        let s1 = m11 * m23 - m21 * m13;
+        let s2 = m11 * m24 - m21 * m14;
+        let s3 = m12 * m23 - m22 * m13;
+        let s4 = m12 * m24 - m22 * m14;
+        let s5 = m13 * m24 - m23 * m14;
+
+        let c5 = m33 * m44 - m43 * m34;
+        let c4 = m32 * m44 - m42 * m34;
+        let c3 = m32 * m43 - m42 * m33;
+        let c2 = m31 * m44 - m41 * m34;
+        let c1 = m31 * m43 - m41 * m33;
+        let c0 = m31 * m42 - m41 * m32;
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+
+        let _0: T = Zero::zero();
+        let _1: T = One::one();
+        if det == _0 {
+            return None;
+        }
+        let inv_det = _1 / det;
+
+        Some(TypedMatrix4D::row_major(
+            ( m22 * c5 - m23 * c4 + m24 * c3) * inv_det,
+            (_0 - m12 * c5 + m13 * c4 - m14 * c3) * inv_det,
+            ( m42 * s5 - m43 * s4 + m44 * s3) * inv_det,
+            (_0 - m32 * s5 + m33 * s4 - m34 * s3) * inv_det,
+
+            (_0 - m21 * c5 + m23 * c2 - m24 * c1) * inv_det,
+            ( m11 * c5 - m13 * c2 + m14 * c1) * inv_det,
+            (_0 - m41 * s5 + m43 * s2 - m44 * s1) * inv_det,
+            ( m31 * s5 - m33 * s2 + m34 * s1) * inv_det,
+
+            ( m21 * c4 - m22 * c2 + m24 * c0) * inv_det,
+            (_0 - m11 * c4 + m12 * c2 - m14 * c0) * inv_det,
+            ( m41 * s4 - m42 * s2 + m44 * s0) * inv_det,
+            (_0 - m31 * s4 + m32 * s2 - m34 * s0) * inv_det,
+
+            (_0 - m21 * c3 + m22 * c1 - m23 * c0) * inv_det,
+            ( m11 * c3 - m12 * c1 + m13 * c0) * inv_det,
+            (_0 - m41 * s3 + m42 * s1 - m43 * s0) * inv_det,
+            ( m31 * s3 - m32 * s1 + m33 * s0) * inv_det,
+        ))
+    }
+
+    /// Returns whether this matrix only maps z = 0 to z = 0 and performs no perspective
+    /// divide, i.e. it could have been produced by `TypedMatrix2D::to_3d`.
+    fn is_2d(&self) -> bool {
+        let (_0, _1) = (Zero::zero(), One::one());
+        self.m13 == _0 && self.m14 == _0 &&
+        self.m23 == _0 && self.m24 == _0 &&
+        self.m31 == _0 && self.m32 == _0 && self.m33 == _1 && self.m34 == _0 &&
+        self.m43 == _0 && self.m44 == _1
+    }
+
+    /// Returns the equivalent 2d matrix, if this matrix is a purely affine transformation
+    /// that does not touch the z axis.
+    pub fn to_2d(&self) -> Option<::matrix2d::TypedMatrix2D<T, Src, Dst>> {
+        if !self.is_2d() {
+            return None;
+        }
+        Some(::matrix2d::TypedMatrix2D::row_major(
+            self.m11, self.m12,
+            self.m21, self.m22,
+            self.m41, self.m42,
+        ))
+    }
+}
+
+impl<T: ApproxEq<T>, Src, Dst> TypedMatrix4D<T, Src, Dst> {
+    pub fn approx_eq(&self, other: &Self) -> bool {
+        self.m11.approx_eq(&other.m11) && self.m12.approx_eq(&other.m12) &&
+        self.m13.approx_eq(&other.m13) && self.m14.approx_eq(&other.m14) &&
+        self.m21.approx_eq(&other.m21) && self.m22.approx_eq(&other.m22) &&
+        self.m23.approx_eq(&other.m23) && self.m24.approx_eq(&other.m24) &&
+        self.m31.approx_eq(&other.m31) && self.m32.approx_eq(&other.m32) &&
+        self.m33.approx_eq(&other.m33) && self.m34.approx_eq(&other.m34) &&
+        self.m41.approx_eq(&other.m41) && self.m42.approx_eq(&other.m42) &&
+        self.m43.approx_eq(&other.m43) && self.m44.approx_eq(&other.m44)
+    }
+}
+
+impl<T: Copy, Src, Dst> Clone for TypedMatrix4D<T, Src, Dst> {
+    fn clone(&self) -> TypedMatrix4D<T, Src, Dst> { *self }
+}
+
+impl<T: Copy, Src, Dst> Copy for TypedMatrix4D<T, Src, Dst> {}
+
+impl<T: PartialEq, Src, Dst> PartialEq<TypedMatrix4D<T, Src, Dst>> for TypedMatrix4D<T, Src, Dst> {
+    fn eq(&self, other: &TypedMatrix4D<T, Src, Dst>) -> bool {
+        self.m11 == other.m11 && self.m12 == other.m12 && self.m13 == other.m13 && self.m14 == other.m14 &&
+        self.m21 == other.m21 && self.m22 == other.m22 && self.m23 == other.m23 && self.m24 == other.m24 &&
+        self.m31 == other.m31 && self.m32 == other.m32 && self.m33 == other.m33 && self.m34 == other.m34 &&
+        self.m41 == other.m41 && self.m42 == other.m42 && self.m43 == other.m43 && self.m44 == other.m44
+    }
+}
+
+impl<T: Copy + fmt::Debug, Src, Dst> fmt::Debug for TypedMatrix4D<T, Src, Dst> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_row_major_array().fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Copy + serde::Serialize, Src, Dst> serde::Serialize for TypedMatrix4D<T, Src, Dst> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_row_major_array().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Copy + serde::Deserialize<'de>, Src, Dst> serde::Deserialize<'de> for TypedMatrix4D<T, Src, Dst> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [
+            m11, m12, m13, m14,
+            m21, m22, m23, m24,
+            m31, m32, m33, m34,
+            m41, m42, m43, m44,
+        ] = try!(<[T; 16]>::deserialize(deserializer));
+        Ok(TypedMatrix4D::row_major(
+            m11, m12, m13, m14,
+            m21, m22, m23, m24,
+            m31, m32, m33, m34,
+            m41, m42, m43, m44,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use approxeq::ApproxEq;
+    use point3d::Point3D;
+    use angle::Angle;
+
+    use std::f32::consts::FRAC_PI_2;
+
+    type Mat = Matrix4D<f32>;
+
+    fn rad(v: f32) -> Angle<f32> { Angle::radians(v) }
+
+    #[test]
+    pub fn test_translation() {
+        let t1 = Mat::create_translation(1.0, 2.0, 3.0);
+        assert_eq!(
+            t1.transform_point3d(&Point3D::new(1.0, 1.0, 1.0)).unwrap(),
+            Point3D::new(2.0, 3.0, 4.0)
+        );
+    }
+
+    #[test]
+    pub fn test_perspective() {
+        let p = Mat::create_perspective(1.0);
+        assert!(p.m34.approx_eq(&-1.0));
+        assert!(p.is_identity() == false);
+    }
+
+    #[test]
+    pub fn test_orthographic() {
+        let m = Mat::create_orthographic(-2.0, 4.0, -1.0, 3.0, 1.0, 10.0);
+
+        let near = m.transform_point3d(&Point3D::new(0.0, 0.0, 1.0)).unwrap();
+        let far = m.transform_point3d(&Point3D::new(0.0, 0.0, 10.0)).unwrap();
+
+        assert!(near.z.approx_eq(&-1.0));
+        assert!(far.z.approx_eq(&1.0));
+    }
+
+    #[test]
+    pub fn test_to_2d_roundtrip() {
+        use matrix2d::Matrix2D;
+        let m2 = Matrix2D::create_rotation(rad(FRAC_PI_2)).post_translated(1.0, 2.0);
+        let m4 = m2.to_3d();
+        assert!(m4.to_2d().unwrap().approx_eq(&m2));
+    }
+
+    #[test]
+    pub fn test_rotation() {
+        let r = Mat::create_rotation(0.0, 0.0, 1.0, rad(FRAC_PI_2));
+        assert!(r.transform_point3d(&Point3D::new(1.0, 0.0, 0.0))
+                 .unwrap().approx_eq(&Point3D::new(0.0, 1.0, 0.0)));
+
+        let r = Mat::create_rotation(1.0, 0.0, 0.0, rad(FRAC_PI_2));
+        assert!(r.transform_point3d(&Point3D::new(0.0, 1.0, 0.0))
+                 .unwrap().approx_eq(&Point3D::new(0.0, 0.0, 1.0)));
+
+        let r = Mat::create_rotation(0.0, 1.0, 0.0, rad(FRAC_PI_2));
+        assert!(r.transform_point3d(&Point3D::new(0.0, 0.0, 1.0))
+                 .unwrap().approx_eq(&Point3D::new(1.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    pub fn test_rotation_matches_2d() {
+        use matrix2d::Matrix2D;
+        let r3 = Mat::create_rotation(0.0, 0.0, 1.0, rad(FRAC_PI_2));
+        let r2 = Matrix2D::create_rotation(rad(FRAC_PI_2)).to_3d();
+        assert!(r3.approx_eq(&r2));
+    }
+
+    #[test]
+    pub fn test_scale() {
+        let s = Mat::create_scale(2.0, 3.0, 4.0);
+        assert!(s.transform_point3d(&Point3D::new(1.0, 1.0, 1.0))
+                 .unwrap().approx_eq(&Point3D::new(2.0, 3.0, 4.0)));
+    }
+
+    #[test]
+    pub fn test_inverse_simple() {
+        let m1 = Mat::identity();
+        let m2 = m1.inverse().unwrap();
+        assert!(m1.approx_eq(&m2));
+    }
+
+    #[test]
+    pub fn test_inverse_translation() {
+        let m1 = Mat::create_translation(-132.0, 0.3, 44.0);
+        let m2 = m1.inverse().unwrap();
+        assert!(m1.pre_mul(&m2).approx_eq(&Mat::identity()));
+    }
+
+    #[test]
+    fn test_size_of() {
+        use std::mem::size_of;
+        assert_eq!(size_of::<Matrix4D<f32>>(), 16 * size_of::<f32>());
+        assert_eq!(size_of::<Matrix4D<f64>>(), 16 * size_of::<f64>());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let m = Mat::create_translation(1.0, 2.0, 3.0);
+        let json = ::serde_json::to_string(&m).unwrap();
+        let deserialized: Mat = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(m, deserialized);
+    }
+}
@@ -0,0 +1,94 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::UnknownUnit;
+use approxeq::ApproxEq;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A 3d point tagged with a unit.
+pub struct TypedPoint3D<T, U> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+    _unit: PhantomData<U>,
+}
+
+/// The default 3d point type with no unit.
+pub type Point3D<T> = TypedPoint3D<T, UnknownUnit>;
+
+impl<T: Copy, U> TypedPoint3D<T, U> {
+    /// Constructor.
+    pub fn new(x: T, y: T, z: T) -> TypedPoint3D<T, U> {
+        TypedPoint3D { x: x, y: y, z: z, _unit: PhantomData }
+    }
+
+    /// Drop the units, preserving only the numeric value.
+    pub fn to_untyped(&self) -> Point3D<T> {
+        Point3D::new(self.x, self.y, self.z)
+    }
+
+    /// Tag a unitless value with units.
+    pub fn from_untyped(p: &Point3D<T>) -> TypedPoint3D<T, U> {
+        TypedPoint3D::new(p.x, p.y, p.z)
+    }
+}
+
+impl<T: ApproxEq<T>, U> TypedPoint3D<T, U> {
+    pub fn approx_eq(&self, other: &Self) -> bool {
+        self.x.approx_eq(&other.x) && self.y.approx_eq(&other.y) && self.z.approx_eq(&other.z)
+    }
+}
+
+impl<T: Copy, U> Clone for TypedPoint3D<T, U> {
+    fn clone(&self) -> TypedPoint3D<T, U> { *self }
+}
+
+impl<T: Copy, U> Copy for TypedPoint3D<T, U> {}
+
+impl<T: PartialEq, U> PartialEq<TypedPoint3D<T, U>> for TypedPoint3D<T, U> {
+    fn eq(&self, other: &TypedPoint3D<T, U>) -> bool {
+        self.x == other.x && self.y == other.y && self.z == other.z
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for TypedPoint3D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?},{:?},{:?})", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Copy + serde::Serialize, U> serde::Serialize for TypedPoint3D<T, U> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self.x, self.y, self.z].serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Copy + serde::Deserialize<'de>, U> serde::Deserialize<'de> for TypedPoint3D<T, U> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, y, z] = try!(<[T; 3]>::deserialize(deserializer));
+        Ok(TypedPoint3D::new(x, y, z))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_roundtrip() {
+        let p = Point3D::new(1.0f32, 2.0, 3.0);
+        let json = ::serde_json::to_string(&p).unwrap();
+        let deserialized: Point3D<f32> = ::serde_json::from_str(&json).unwrap();
+        assert_eq!(p, deserialized);
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2013 The Servo Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::UnknownUnit;
+use approxeq::ApproxEq;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// A 2d displacement, tagged with a unit, as opposed to a `TypedPoint2D` which is a position.
+///
+/// Vectors ignore translation when run through a matrix: a `TypedMatrix2D::transform_vector`
+/// call only applies the linear part of the transformation, so that transforming a normal,
+/// velocity or other delta does not get corrupted by the matrix's translation terms.
+pub struct TypedVector2D<T, U> {
+    pub x: T,
+    pub y: T,
+    _unit: PhantomData<U>,
+}
+
+/// The default 2d vector type with no unit.
+pub type Vector2D<T> = TypedVector2D<T, UnknownUnit>;
+
+impl<T: Copy, U> TypedVector2D<T, U> {
+    /// Constructor.
+    pub fn new(x: T, y: T) -> TypedVector2D<T, U> {
+        TypedVector2D { x: x, y: y, _unit: PhantomData }
+    }
+
+    /// Drop the units, preserving only the numeric value.
+    pub fn to_untyped(&self) -> Vector2D<T> {
+        Vector2D::new(self.x, self.y)
+    }
+
+    /// Tag a unitless value with units.
+    pub fn from_untyped(v: &Vector2D<T>) -> TypedVector2D<T, U> {
+        TypedVector2D::new(v.x, v.y)
+    }
+}
+
+impl<T: ApproxEq<T>, U> TypedVector2D<T, U> {
+    pub fn approx_eq(&self, other: &Self) -> bool {
+        self.x.approx_eq(&other.x) && self.y.approx_eq(&other.y)
+    }
+}
+
+impl<T: Copy, U> Clone for TypedVector2D<T, U> {
+    fn clone(&self) -> TypedVector2D<T, U> { *self }
+}
+
+impl<T: Copy, U> Copy for TypedVector2D<T, U> {}
+
+impl<T: PartialEq, U> PartialEq<TypedVector2D<T, U>> for TypedVector2D<T, U> {
+    fn eq(&self, other: &TypedVector2D<T, U>) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<T: fmt::Debug, U> fmt::Debug for TypedVector2D<T, U> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({:?},{:?})", self.x, self.y)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Copy + serde::Serialize, U> serde::Serialize for TypedVector2D<T, U> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        [self.x, self.y].serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Copy + serde::Deserialize<'de>, U> serde::Deserialize<'de> for TypedVector2D<T, U> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let [x, y] = try!(<[T; 2]>::deserialize(deserializer));
+        Ok(TypedVector2D::new(x, y))
+    }
+}